@@ -0,0 +1,71 @@
+//! Tauri command handlers exposed to the frontend via `invoke`.
+
+use crate::core::encode_settings::EncodeSettings;
+use crate::core::probe::ProbeResult;
+use crate::core::python::{self, BackendResult, ConvertPayload};
+use tauri::Emitter;
+
+#[tauri::command]
+pub fn ping() -> &'static str {
+    "pong"
+}
+
+#[tauri::command]
+pub async fn convert_audio(
+    app: tauri::AppHandle,
+    payload: ConvertPayload,
+) -> Result<BackendResult, String> {
+    tauri::async_runtime::spawn_blocking(move || python::execute_python_conversion(app, payload))
+        .await
+        .map_err(|e| format!("Conversion task panicked: {}", e))?
+}
+
+#[tauri::command]
+pub async fn analyze_audio(
+    app: tauri::AppHandle,
+    files: Vec<String>,
+    target_format: Option<String>,
+    target_encode_settings: Option<EncodeSettings>,
+) -> Result<Vec<ProbeResult>, String> {
+    let probe_app = app.clone();
+    let mut results =
+        tauri::async_runtime::spawn_blocking(move || python::execute_python_probe(app, files))
+            .await
+            .map_err(|e| format!("Analysis task panicked: {}", e))??;
+
+    // Fill in each file's estimated output size once we know what format the
+    // user has in mind, so the UI can show it before they commit to
+    // converting; the probe step itself has no target to estimate against.
+    if let Some(format) = target_format.as_deref() {
+        for result in &mut results {
+            let settings = EncodeSettings::resolve_for_file(
+                target_encode_settings.clone(),
+                format,
+                Some(result),
+            );
+            result.estimated_output_bytes = result.estimate_output_bytes(&settings);
+        }
+    }
+
+    // Emitted in addition to the returned value so the UI can warn on
+    // lossy-to-lossless "fake upgrades" and preview the estimated output size
+    // as soon as probing finishes.
+    let _ = probe_app.emit("probe-results", &results);
+
+    Ok(results)
+}
+
+#[tauri::command]
+pub fn resolve_encode_preset(name: String) -> Result<EncodeSettings, String> {
+    EncodeSettings::preset(&name).ok_or_else(|| format!("Unknown encode preset '{}'", name))
+}
+
+#[tauri::command]
+pub fn cancel_conversion(app: tauri::AppHandle, job_id: String) -> Result<(), String> {
+    python::cancel_conversion(&app, &job_id)
+}
+
+#[tauri::command]
+pub fn cancel_all_conversions(app: tauri::AppHandle) -> usize {
+    python::cancel_all_conversions(&app)
+}
@@ -0,0 +1,224 @@
+//! Structured encoder settings for the conversion backend.
+//!
+//! Replaces the bare `format: String` the backend used to infer every codec
+//! decision from, so quality controls (bitrate mode, sample rate, channel
+//! layout, loudness normalization) are first-class and reproducible instead
+//! of being guessed on the Python side.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BitrateMode {
+    Cbr,
+    Vbr,
+    TargetQuality,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct EncodeSettings {
+    #[serde(default)]
+    pub codec: Option<String>,
+    pub container: String,
+    #[serde(default)]
+    pub bitrate_mode: Option<BitrateMode>,
+    #[serde(default)]
+    pub bitrate_kbps: Option<u32>,
+    #[serde(default)]
+    pub quality: Option<f32>,
+    #[serde(default)]
+    pub sample_rate_hz: Option<u32>,
+    #[serde(default)]
+    pub channels: Option<u16>,
+    #[serde(default)]
+    pub loudness_target_lufs: Option<f32>,
+}
+
+impl EncodeSettings {
+    /// Look up a named preset (e.g. `"Podcast 128k"`), case-insensitively.
+    pub fn preset(name: &str) -> Option<EncodeSettings> {
+        named_presets()
+            .into_iter()
+            .find(|(preset_name, _)| preset_name.eq_ignore_ascii_case(name))
+            .map(|(_, settings)| settings)
+    }
+
+    /// Per-container fallback used when no explicit settings were supplied.
+    pub fn defaults_for_container(container: &str) -> EncodeSettings {
+        match container.to_ascii_lowercase().as_str() {
+            "flac" => EncodeSettings {
+                codec: Some("flac".to_string()),
+                container: "flac".to_string(),
+                ..Default::default()
+            },
+            "wav" => EncodeSettings {
+                codec: Some("pcm_s16le".to_string()),
+                container: "wav".to_string(),
+                ..Default::default()
+            },
+            "mp3" => EncodeSettings {
+                codec: Some("libmp3lame".to_string()),
+                container: "mp3".to_string(),
+                bitrate_mode: Some(BitrateMode::Vbr),
+                quality: Some(2.0),
+                ..Default::default()
+            },
+            "m4a" | "aac" => EncodeSettings {
+                codec: Some("aac".to_string()),
+                container: "m4a".to_string(),
+                bitrate_mode: Some(BitrateMode::Cbr),
+                bitrate_kbps: Some(192),
+                ..Default::default()
+            },
+            "ogg" => EncodeSettings {
+                codec: Some("libvorbis".to_string()),
+                container: "ogg".to_string(),
+                bitrate_mode: Some(BitrateMode::Vbr),
+                quality: Some(5.0),
+                ..Default::default()
+            },
+            other => EncodeSettings {
+                codec: None,
+                container: other.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Fill any field left unset with the per-container default, so the
+    /// backend always receives a fully-specified request.
+    pub fn with_defaults(self) -> EncodeSettings {
+        let defaults = Self::defaults_for_container(&self.container);
+        EncodeSettings {
+            codec: self.codec.or(defaults.codec),
+            container: self.container,
+            bitrate_mode: self.bitrate_mode.or(defaults.bitrate_mode),
+            bitrate_kbps: self.bitrate_kbps.or(defaults.bitrate_kbps),
+            quality: self.quality.or(defaults.quality),
+            sample_rate_hz: self.sample_rate_hz.or(defaults.sample_rate_hz),
+            channels: self.channels.or(defaults.channels),
+            loudness_target_lufs: self.loudness_target_lufs,
+        }
+    }
+
+    /// Resolve the settings that should actually be sent to the backend for a
+    /// conversion: the caller's explicit settings take priority, falling back
+    /// to per-container defaults derived from the legacy `format` string.
+    pub fn resolve(explicit: Option<EncodeSettings>, format: &str) -> EncodeSettings {
+        explicit
+            .unwrap_or_else(|| Self::defaults_for_container(format))
+            .with_defaults()
+    }
+}
+
+fn named_presets() -> Vec<(&'static str, EncodeSettings)> {
+    vec![
+        (
+            "Lossless",
+            EncodeSettings {
+                codec: Some("flac".to_string()),
+                container: "flac".to_string(),
+                ..Default::default()
+            },
+        ),
+        (
+            "Archive FLAC",
+            EncodeSettings {
+                codec: Some("flac".to_string()),
+                container: "flac".to_string(),
+                sample_rate_hz: Some(96_000),
+                ..Default::default()
+            },
+        ),
+        (
+            "Podcast 128k",
+            EncodeSettings {
+                codec: Some("aac".to_string()),
+                container: "m4a".to_string(),
+                bitrate_mode: Some(BitrateMode::Cbr),
+                bitrate_kbps: Some(128),
+                sample_rate_hz: Some(44_100),
+                channels: Some(1),
+                loudness_target_lufs: Some(-16.0),
+                ..Default::default()
+            },
+        ),
+        (
+            "Archive MP3 VBR",
+            EncodeSettings {
+                codec: Some("libmp3lame".to_string()),
+                container: "mp3".to_string(),
+                bitrate_mode: Some(BitrateMode::Vbr),
+                quality: Some(0.0),
+                ..Default::default()
+            },
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preset_looks_up_case_insensitively() {
+        let preset = EncodeSettings::preset("podcast 128k").unwrap();
+        assert_eq!(preset.container, "m4a");
+        assert_eq!(preset.bitrate_kbps, Some(128));
+    }
+
+    #[test]
+    fn preset_returns_none_for_an_unknown_name() {
+        assert!(EncodeSettings::preset("Nonexistent Preset").is_none());
+    }
+
+    #[test]
+    fn defaults_for_container_falls_back_to_a_bare_container_for_unknown_formats() {
+        let defaults = EncodeSettings::defaults_for_container("webm");
+        assert_eq!(defaults.codec, None);
+        assert_eq!(defaults.container, "webm");
+    }
+
+    #[test]
+    fn defaults_for_container_picks_known_container_defaults() {
+        let defaults = EncodeSettings::defaults_for_container("mp3");
+        assert_eq!(defaults.codec, Some("libmp3lame".to_string()));
+        assert_eq!(defaults.bitrate_mode, Some(BitrateMode::Vbr));
+    }
+
+    #[test]
+    fn with_defaults_only_fills_unset_fields() {
+        let settings = EncodeSettings {
+            container: "mp3".to_string(),
+            bitrate_kbps: Some(320),
+            ..Default::default()
+        }
+        .with_defaults();
+
+        // Explicit field is untouched...
+        assert_eq!(settings.bitrate_kbps, Some(320));
+        // ...but unset fields still pick up the container's defaults.
+        assert_eq!(settings.codec, Some("libmp3lame".to_string()));
+    }
+
+    #[test]
+    fn resolve_prefers_explicit_settings_over_the_format_default() {
+        let explicit = EncodeSettings {
+            container: "flac".to_string(),
+            ..Default::default()
+        };
+
+        let resolved = EncodeSettings::resolve(Some(explicit), "mp3");
+
+        assert_eq!(resolved.container, "flac");
+        assert_eq!(resolved.codec, Some("flac".to_string()));
+    }
+
+    #[test]
+    fn resolve_falls_back_to_the_format_default_when_no_explicit_settings_given() {
+        let resolved = EncodeSettings::resolve(None, "mp3");
+
+        assert_eq!(resolved.container, "mp3");
+        assert_eq!(resolved.codec, Some("libmp3lame".to_string()));
+    }
+}
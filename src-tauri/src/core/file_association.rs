@@ -0,0 +1,148 @@
+//! File-association support: turning OS-provided file paths (double-click,
+//! "Open With", or a deep link) into validated paths the frontend can load.
+
+use std::path::{Path, PathBuf};
+
+const SUPPORTED_AUDIO_EXTENSIONS: &[&str] = &[
+    "mp3", "wav", "flac", "ogg", "oga", "m4a", "aac", "wma", "aiff", "aif", "opus",
+];
+
+/// Whether `path` has one of the extensions this app knows how to convert.
+pub fn is_supported_audio_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            SUPPORTED_AUDIO_EXTENSIONS
+                .iter()
+                .any(|supported| supported.eq_ignore_ascii_case(ext))
+        })
+}
+
+/// Pull out the file paths the OS handed us on the command line (skipping the
+/// binary's own path and any `--flag`-style arguments): a plain filesystem
+/// path or `file://` URL must exist and look like a supported audio file,
+/// while any other `scheme://...` deep-link argument (can't be checked for
+/// existence, since it isn't a filesystem path) is kept purely on its extension.
+pub fn extract_audio_paths<I, S>(args: I) -> Vec<PathBuf>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter()
+        .skip(1)
+        .filter(|arg| !arg.as_ref().starts_with('-'))
+        .filter_map(|arg| resolve_candidate(arg.as_ref()))
+        .collect()
+}
+
+/// Pull out supported audio file paths from the URLs `tauri-plugin-deep-link`
+/// hands to its `on_open_url` callback.
+pub fn extract_audio_paths_from_urls<I, S>(urls: I) -> Vec<PathBuf>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    urls.into_iter()
+        .filter_map(|url| resolve_candidate(url.as_ref()))
+        .collect()
+}
+
+/// Turn a single CLI argument or deep-link URL into a path, if it looks like
+/// a supported audio file. A `file://` URL names a real filesystem path, so
+/// it's held to the same existence check as a plain argument; any other
+/// `scheme://` deep link isn't a filesystem path at all and can only be
+/// judged on its extension.
+fn resolve_candidate(arg: &str) -> Option<PathBuf> {
+    if let Some((path, is_filesystem_path)) = uri_to_path(arg) {
+        let exists_or_unknowable = !is_filesystem_path || path.exists();
+        (exists_or_unknowable && is_supported_audio_path(&path)).then_some(path)
+    } else {
+        let path = PathBuf::from(arg);
+        (path.exists() && is_supported_audio_path(&path)).then_some(path)
+    }
+}
+
+/// Extract the path out of a `scheme://` URI, percent-decoding it, along with
+/// whether that path names a real filesystem location (`file://`) as opposed
+/// to an opaque deep-link path component that just happens to look like one.
+/// Returns `None` for anything that isn't `scheme://`-shaped, so plain
+/// filesystem paths fall through to the existence check instead.
+fn uri_to_path(value: &str) -> Option<(PathBuf, bool)> {
+    let (scheme, rest) = value.split_once("://")?;
+    if scheme.eq_ignore_ascii_case("file") {
+        return Some((PathBuf::from(percent_decode(rest)), true));
+    }
+
+    let path_only = rest.split(['?', '#']).next().unwrap_or(rest);
+    (!path_only.is_empty()).then(|| (PathBuf::from(percent_decode(path_only)), false))
+}
+
+/// Decode `%XX` percent-escapes (e.g. `%20` -> a space) in a URI path
+/// component. Bytes that don't form a valid escape are passed through
+/// unchanged; the result is treated as UTF-8, lossily, since filesystem
+/// paths in deep links are expected to be valid UTF-8.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 3 <= bytes.len()
+            && bytes[i + 1].is_ascii_hexdigit()
+            && bytes[i + 2].is_ascii_hexdigit()
+        {
+            if let Ok(byte) = u8::from_str_radix(std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or(""), 16) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_decode_handles_encoded_spaces() {
+        assert_eq!(percent_decode("My%20Song.mp3"), "My Song.mp3");
+    }
+
+    #[test]
+    fn percent_decode_passes_through_invalid_escapes() {
+        assert_eq!(percent_decode("100%done.mp3"), "100%done.mp3");
+    }
+
+    #[test]
+    fn extract_audio_paths_from_urls_rejects_a_nonexistent_file_url() {
+        let paths =
+            extract_audio_paths_from_urls(["file:///does/not/exist.mp3".to_string()]);
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    fn extract_audio_paths_from_urls_keeps_an_opaque_deep_link_without_checking_existence() {
+        let paths = extract_audio_paths_from_urls([
+            "soundconverter://does/not/exist.mp3".to_string(),
+        ]);
+        assert_eq!(paths, vec![PathBuf::from("does/not/exist.mp3")]);
+    }
+
+    #[test]
+    fn extract_audio_paths_from_urls_accepts_an_existing_file_url() {
+        let file = std::env::temp_dir().join("soundconverter_file_association_test.mp3");
+        std::fs::write(&file, b"").unwrap();
+
+        let url = format!("file://{}", file.display());
+        let paths = extract_audio_paths_from_urls([url]);
+
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(paths, vec![file]);
+    }
+}
@@ -0,0 +1,6 @@
+//! Thin logging helper that routes backend and Python subprocess output through
+//! the `tauri-plugin-log` sink so it shows up alongside the rest of the app's logs.
+
+pub fn log_message(source: &str, message: &str) {
+    log::info!(target: "soundconverter", "[{}] {}", source, message);
+}
@@ -0,0 +1,9 @@
+//! Core backend functionality shared across Tauri commands.
+
+pub mod encode_settings;
+pub mod file_association;
+pub mod logging;
+pub mod probe;
+pub mod progress;
+pub mod python;
+pub mod sandbox_env;
@@ -0,0 +1,291 @@
+//! Structured media-probe results used to pick smart conversion defaults
+//! instead of converting blind: matching the source sample rate/channel
+//! layout, skipping a re-encode that wouldn't change anything, and warning
+//! the user before a lossy source gets "upgraded" into a lossless container.
+
+use crate::core::encode_settings::EncodeSettings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProbeResult {
+    pub file: String,
+    pub container: String,
+    pub codec: String,
+    pub sample_rate_hz: Option<u32>,
+    pub bit_depth: Option<u16>,
+    pub channels: Option<u16>,
+    pub duration_seconds: Option<f64>,
+    pub integrated_loudness_lufs: Option<f32>,
+    #[serde(default)]
+    pub lossy: bool,
+    /// Estimated output size in bytes for the conversion the caller has in
+    /// mind, filled in by `commands::analyze_audio` via
+    /// [`ProbeResult::estimate_output_bytes`] once a target format is known;
+    /// the Python probe step itself has no target to estimate against.
+    #[serde(default)]
+    pub estimated_output_bytes: Option<u64>,
+}
+
+impl ProbeResult {
+    /// True when `target_codec` is lossless but the source itself is lossy,
+    /// i.e. the conversion can't recover any quality the source already lost.
+    pub fn is_fake_upgrade(&self, target_codec: &str) -> bool {
+        self.lossy && !is_lossy_codec(target_codec)
+    }
+
+    /// Roughly estimate the output size in bytes if this source were encoded
+    /// with `target`, so the user can see the cost before committing.
+    /// Returns `None` when the size can't be predicted from `target` alone -
+    /// an uncompressed PCM codec is computed directly from sample
+    /// rate/channels/bit depth and duration; any other codec needs an
+    /// explicit `bitrate_kbps` (a bare `quality` target, e.g. MP3 VBR, has no
+    /// fixed bitrate to multiply by, and a compressed lossless codec like
+    /// FLAC has no predictable ratio).
+    pub fn estimate_output_bytes(&self, target: &EncodeSettings) -> Option<u64> {
+        let duration_seconds = self.duration_seconds?;
+
+        if let Some(bit_depth) = target.codec.as_deref().and_then(pcm_bit_depth) {
+            let sample_rate_hz = target.sample_rate_hz.or(self.sample_rate_hz)?;
+            let channels = target.channels.or(self.channels).unwrap_or(2);
+            let bytes_per_second =
+                sample_rate_hz as f64 * channels as f64 * bit_depth as f64 / 8.0;
+            return Some((duration_seconds * bytes_per_second).max(0.0) as u64);
+        }
+
+        let bitrate_kbps = target.bitrate_kbps?;
+        Some((duration_seconds * bitrate_kbps as f64 * 1000.0 / 8.0).max(0.0) as u64)
+    }
+}
+
+fn is_lossy_codec(codec: &str) -> bool {
+    matches!(
+        codec.to_ascii_lowercase().as_str(),
+        "mp3" | "libmp3lame" | "aac" | "vorbis" | "libvorbis" | "opus" | "libopus" | "wma"
+    )
+}
+
+/// Bits per sample for an FFmpeg PCM codec name, or `None` if `codec` isn't a
+/// (predictably-sized) PCM codec.
+fn pcm_bit_depth(codec: &str) -> Option<u16> {
+    match codec.to_ascii_lowercase().as_str() {
+        "pcm_u8" | "pcm_s8" => Some(8),
+        "pcm_s16le" | "pcm_s16be" => Some(16),
+        "pcm_s24le" | "pcm_s24be" => Some(24),
+        "pcm_s32le" | "pcm_s32be" | "pcm_f32le" | "pcm_f32be" => Some(32),
+        _ => None,
+    }
+}
+
+impl EncodeSettings {
+    /// Like [`EncodeSettings::resolve`], but additionally prefers the probed
+    /// input's sample rate/channel layout over the container default: never
+    /// upsamples (e.g. 44.1k -> 48k) or changes channel count unless the
+    /// caller explicitly asked for it.
+    pub fn resolve_for_file(
+        explicit: Option<EncodeSettings>,
+        format: &str,
+        probe: Option<&ProbeResult>,
+    ) -> EncodeSettings {
+        let explicit_sample_rate = explicit.as_ref().and_then(|e| e.sample_rate_hz);
+        let explicit_channels = explicit.as_ref().and_then(|e| e.channels);
+
+        let mut settings = Self::resolve(explicit, format);
+
+        if let Some(probe) = probe {
+            if explicit_sample_rate.is_none() {
+                settings.sample_rate_hz = match (settings.sample_rate_hz, probe.sample_rate_hz) {
+                    (Some(target), Some(source)) if target > source => Some(source),
+                    (None, source) => source,
+                    (target, _) => target,
+                };
+            }
+
+            if explicit_channels.is_none() && settings.channels.is_none() {
+                settings.channels = probe.channels;
+            }
+        }
+
+        settings
+    }
+
+    /// Whether encoding against `probe` would produce the same codec,
+    /// container, sample rate, and channel layout the source already has -
+    /// i.e. conversion can take a passthrough fast path instead of re-encoding.
+    pub fn matches_source(&self, probe: &ProbeResult) -> bool {
+        let codec_matches = self
+            .codec
+            .as_deref()
+            .is_some_and(|codec| codec.eq_ignore_ascii_case(&probe.codec));
+        let container_matches = self.container.eq_ignore_ascii_case(&probe.container);
+        let rate_matches = self
+            .sample_rate_hz
+            .zip(probe.sample_rate_hz)
+            .map(|(target, source)| target == source)
+            .unwrap_or(true);
+        let channels_match = self
+            .channels
+            .zip(probe.channels)
+            .map(|(target, source)| target == source)
+            .unwrap_or(true);
+
+        codec_matches && container_matches && rate_matches && channels_match
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::encode_settings::BitrateMode;
+
+    fn probe(codec: &str, container: &str, sample_rate_hz: Option<u32>, channels: Option<u16>) -> ProbeResult {
+        ProbeResult {
+            file: "source.flac".to_string(),
+            container: container.to_string(),
+            codec: codec.to_string(),
+            sample_rate_hz,
+            bit_depth: None,
+            channels,
+            duration_seconds: None,
+            integrated_loudness_lufs: None,
+            lossy: false,
+            estimated_output_bytes: None,
+        }
+    }
+
+    #[test]
+    fn resolve_for_file_prefers_explicit_sample_rate_and_channels() {
+        let explicit = EncodeSettings {
+            sample_rate_hz: Some(48_000),
+            channels: Some(2),
+            ..EncodeSettings::defaults_for_container("flac")
+        };
+        let source = probe("flac", "flac", Some(44_100), Some(1));
+
+        let resolved = EncodeSettings::resolve_for_file(Some(explicit), "flac", Some(&source));
+
+        assert_eq!(resolved.sample_rate_hz, Some(48_000));
+        assert_eq!(resolved.channels, Some(2));
+    }
+
+    #[test]
+    fn resolve_for_file_fills_gaps_from_the_probed_source() {
+        let source = probe("flac", "flac", Some(44_100), Some(1));
+
+        let resolved = EncodeSettings::resolve_for_file(None, "flac", Some(&source));
+
+        assert_eq!(resolved.sample_rate_hz, Some(44_100));
+        assert_eq!(resolved.channels, Some(1));
+    }
+
+    #[test]
+    fn resolve_for_file_keeps_an_explicit_sample_rate_even_above_the_source() {
+        // An explicit ask (here, a preset) is allowed to upsample; only an
+        // *implicit*, container-default rate is capped to the source.
+        let preset = EncodeSettings::preset("Archive FLAC").unwrap();
+        assert_eq!(preset.sample_rate_hz, Some(96_000));
+        let source = probe("flac", "flac", Some(44_100), None);
+
+        let resolved = EncodeSettings::resolve_for_file(Some(preset), "flac", Some(&source));
+        assert_eq!(resolved.sample_rate_hz, Some(96_000));
+    }
+
+    #[test]
+    fn resolve_for_file_leaves_explicit_channels_alone_even_without_a_probe() {
+        let explicit = EncodeSettings {
+            channels: Some(2),
+            ..EncodeSettings::defaults_for_container("flac")
+        };
+
+        let resolved = EncodeSettings::resolve_for_file(Some(explicit), "flac", None);
+
+        assert_eq!(resolved.channels, Some(2));
+    }
+
+    #[test]
+    fn matches_source_detects_an_exact_passthrough_match() {
+        let settings = EncodeSettings {
+            codec: Some("FLAC".to_string()),
+            container: "FLAC".to_string(),
+            sample_rate_hz: Some(44_100),
+            channels: Some(2),
+            ..Default::default()
+        };
+        let source = probe("flac", "flac", Some(44_100), Some(2));
+
+        assert!(settings.matches_source(&source));
+    }
+
+    #[test]
+    fn matches_source_rejects_a_sample_rate_mismatch() {
+        let settings = EncodeSettings {
+            codec: Some("flac".to_string()),
+            container: "flac".to_string(),
+            sample_rate_hz: Some(48_000),
+            ..Default::default()
+        };
+        let source = probe("flac", "flac", Some(44_100), None);
+
+        assert!(!settings.matches_source(&source));
+    }
+
+    fn with_duration(mut result: ProbeResult, duration_seconds: f64) -> ProbeResult {
+        result.duration_seconds = Some(duration_seconds);
+        result
+    }
+
+    #[test]
+    fn estimate_output_bytes_from_a_fixed_bitrate() {
+        let source = with_duration(probe("flac", "flac", Some(44_100), Some(2)), 10.0);
+        let target = EncodeSettings {
+            codec: Some("aac".to_string()),
+            container: "m4a".to_string(),
+            bitrate_kbps: Some(128),
+            ..Default::default()
+        };
+
+        // 128kbps * 10s / 8 bits-per-byte = 160,000 bytes.
+        assert_eq!(source.estimate_output_bytes(&target), Some(160_000));
+    }
+
+    #[test]
+    fn estimate_output_bytes_from_uncompressed_pcm() {
+        let source = with_duration(probe("flac", "flac", None, None), 1.0);
+        let target = EncodeSettings {
+            codec: Some("pcm_s16le".to_string()),
+            container: "wav".to_string(),
+            sample_rate_hz: Some(44_100),
+            channels: Some(2),
+            ..Default::default()
+        };
+
+        // 44_100 Hz * 2 channels * 2 bytes-per-sample * 1s = 176,400 bytes.
+        assert_eq!(source.estimate_output_bytes(&target), Some(176_400));
+    }
+
+    #[test]
+    fn estimate_output_bytes_is_none_without_a_fixed_bitrate_or_pcm_codec() {
+        let source = with_duration(probe("flac", "flac", Some(44_100), Some(2)), 10.0);
+        let target = EncodeSettings {
+            codec: Some("libmp3lame".to_string()),
+            container: "mp3".to_string(),
+            bitrate_mode: Some(BitrateMode::Vbr),
+            quality: Some(2.0),
+            ..Default::default()
+        };
+
+        assert_eq!(source.estimate_output_bytes(&target), None);
+    }
+
+    #[test]
+    fn estimate_output_bytes_is_none_without_a_known_duration() {
+        let source = probe("flac", "flac", Some(44_100), Some(2));
+        let target = EncodeSettings {
+            codec: Some("aac".to_string()),
+            container: "m4a".to_string(),
+            bitrate_kbps: Some(128),
+            ..Default::default()
+        };
+
+        assert_eq!(source.estimate_output_bytes(&target), None);
+    }
+}
@@ -0,0 +1,265 @@
+//! Typed `conversion-progress` event schema plus the bookkeeping needed to
+//! fill it in: a rolling per-file ETA estimate, a batch-wide ETA aggregated
+//! from it, and a batch-wide percentage that stays monotonically
+//! non-decreasing even when the worker pool finishes files out of order.
+
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Bumped whenever [`ProgressEvent`]'s shape changes in a way the frontend
+/// needs to branch on.
+pub const PROGRESS_EVENT_VERSION: u32 = 1;
+
+/// A `conversion-progress` payload, tagged so the frontend can match on
+/// `event` instead of guessing at which optional fields are present.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    Started {
+        job_id: String,
+        file_index: usize,
+        file: String,
+    },
+    Tick {
+        job_id: String,
+        file_index: usize,
+        file: String,
+        /// Normalized to 0-100 regardless of what the backend reported.
+        percent: f32,
+        processed_seconds: Option<f32>,
+        total_seconds: Option<f32>,
+        /// `None` until the rolling throughput estimate has at least one
+        /// sample to work from.
+        eta_seconds: Option<f32>,
+        batch_percent: f32,
+        /// Sum of every file's latest remaining-seconds estimate. `None`
+        /// until at least one file in the batch has an ETA estimate.
+        batch_eta_seconds: Option<f32>,
+    },
+    FileComplete {
+        job_id: String,
+        file_index: usize,
+        file: String,
+        batch_percent: f32,
+    },
+    BatchComplete {
+        total_files: usize,
+        completed_files: usize,
+        batch_eta_seconds: Option<f32>,
+    },
+    Error {
+        job_id: String,
+        file_index: usize,
+        file: String,
+        message: String,
+    },
+}
+
+/// Envelope emitted on `conversion-progress`; the `version` field lets the
+/// frontend detect a schema it doesn't understand instead of silently
+/// misreading it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VersionedProgressEvent {
+    pub version: u32,
+    #[serde(flatten)]
+    pub event: ProgressEvent,
+}
+
+impl VersionedProgressEvent {
+    pub fn new(event: ProgressEvent) -> Self {
+        Self {
+            version: PROGRESS_EVENT_VERSION,
+            event,
+        }
+    }
+}
+
+/// Estimates remaining time for a single file from how much audio it has
+/// processed over wall-clock time, smoothing the rate so one slow or bursty
+/// sample doesn't cause the ETA to jump around.
+#[derive(Debug, Default)]
+pub struct ThroughputEstimator {
+    smoothed_rate: Option<f32>,
+    last_sample: Option<(Instant, f32)>,
+}
+
+impl ThroughputEstimator {
+    /// Weight given to each new sample when updating the smoothed rate; the
+    /// rest is carried over from the previous estimate.
+    const SMOOTHING_FACTOR: f32 = 0.3;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a new `processed_seconds` reading and return the estimated
+    /// seconds remaining for `total_seconds` of audio, or `None` until a rate
+    /// can be estimated (first sample, or no progress made between samples).
+    pub fn observe(&mut self, processed_seconds: f32, total_seconds: Option<f32>) -> Option<f32> {
+        let now = Instant::now();
+
+        if let Some((last_time, last_processed)) = self.last_sample {
+            let wall_elapsed = now.duration_since(last_time).as_secs_f32();
+            let audio_elapsed = processed_seconds - last_processed;
+            if wall_elapsed > 0.0 && audio_elapsed >= 0.0 {
+                let sample_rate = audio_elapsed / wall_elapsed;
+                self.smoothed_rate = Some(match self.smoothed_rate {
+                    Some(rate) => rate + Self::SMOOTHING_FACTOR * (sample_rate - rate),
+                    None => sample_rate,
+                });
+            }
+        }
+        self.last_sample = Some((now, processed_seconds));
+
+        let rate = self.smoothed_rate.filter(|rate| *rate > 0.0)?;
+        let total_seconds = total_seconds?;
+        let remaining = (total_seconds - processed_seconds).max(0.0);
+        Some(remaining / rate)
+    }
+}
+
+/// Combines each file's own percent-complete into one batch-wide percentage.
+/// Workers report progress out of order and not every file starts at the
+/// same time, so the batch percentage is clamped to never go backwards.
+#[derive(Debug)]
+pub struct BatchProgress {
+    per_file_percent: Vec<f32>,
+    /// Each file's most recent remaining-seconds estimate from its own
+    /// [`ThroughputEstimator`]; `None` until that file has reported a tick
+    /// with enough samples to estimate a rate.
+    per_file_eta_seconds: Vec<Option<f32>>,
+    last_emitted_percent: f32,
+}
+
+impl BatchProgress {
+    pub fn new(total_files: usize) -> Self {
+        Self {
+            per_file_percent: vec![0.0; total_files],
+            per_file_eta_seconds: vec![None; total_files],
+            last_emitted_percent: 0.0,
+        }
+    }
+
+    /// Record `percent` (any range; clamped to 0-100) for `file_index` and
+    /// return the batch-wide percentage.
+    pub fn update(&mut self, file_index: usize, percent: f32) -> f32 {
+        if let Some(slot) = self.per_file_percent.get_mut(file_index) {
+            *slot = percent.clamp(0.0, 100.0);
+        }
+
+        let average = if self.per_file_percent.is_empty() {
+            100.0
+        } else {
+            self.per_file_percent.iter().sum::<f32>() / self.per_file_percent.len() as f32
+        };
+
+        self.last_emitted_percent = self.last_emitted_percent.max(average);
+        self.last_emitted_percent
+    }
+
+    pub fn complete_file(&mut self, file_index: usize) -> f32 {
+        if let Some(slot) = self.per_file_eta_seconds.get_mut(file_index) {
+            *slot = Some(0.0);
+        }
+        self.update(file_index, 100.0)
+    }
+
+    /// Record `file_index`'s latest per-file ETA (leaving the previous value
+    /// in place when `eta_seconds` is `None`, e.g. between samples) and
+    /// return the batch-wide ETA: the sum of every file's latest known
+    /// remaining-seconds estimate. `None` until at least one file has an
+    /// estimate.
+    pub fn update_eta(&mut self, file_index: usize, eta_seconds: Option<f32>) -> Option<f32> {
+        if let Some(slot) = self.per_file_eta_seconds.get_mut(file_index) {
+            *slot = eta_seconds.or(*slot);
+        }
+        self.batch_eta_seconds()
+    }
+
+    /// The sum of every file's latest known remaining-seconds estimate,
+    /// without recording a new sample.
+    pub fn batch_eta_seconds(&self) -> Option<f32> {
+        let known: Vec<f32> = self.per_file_eta_seconds.iter().filter_map(|eta| *eta).collect();
+        if known.is_empty() {
+            None
+        } else {
+            Some(known.iter().sum())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    #[test]
+    fn throughput_estimator_has_no_estimate_until_two_samples() {
+        let mut estimator = ThroughputEstimator::new();
+        assert_eq!(estimator.observe(0.0, Some(60.0)), None);
+    }
+
+    #[test]
+    fn throughput_estimator_estimates_remaining_time_from_rate() {
+        let mut estimator = ThroughputEstimator::new();
+        estimator.observe(0.0, Some(10.0));
+        sleep(Duration::from_millis(50));
+        // 1 second of audio processed in ~50ms of wall time (roughly 20x
+        // real time), leaving 9 seconds of audio to go.
+        let eta = estimator.observe(1.0, Some(10.0));
+        assert!(eta.is_some_and(|eta| eta > 0.0 && eta < 10.0));
+    }
+
+    #[test]
+    fn throughput_estimator_needs_total_seconds_to_estimate() {
+        let mut estimator = ThroughputEstimator::new();
+        estimator.observe(0.0, None);
+        sleep(Duration::from_millis(10));
+        assert_eq!(estimator.observe(1.0, None), None);
+    }
+
+    #[test]
+    fn batch_progress_averages_across_files() {
+        let mut batch = BatchProgress::new(2);
+        assert_eq!(batch.update(0, 100.0), 50.0);
+        assert_eq!(batch.update(1, 100.0), 100.0);
+    }
+
+    #[test]
+    fn batch_progress_percent_never_decreases() {
+        let mut batch = BatchProgress::new(2);
+        batch.update(0, 80.0);
+        batch.update(1, 80.0);
+        // File 0 reporting a lower percent drags the average down, but the
+        // batch-wide percentage must not go backwards.
+        assert_eq!(batch.update(0, 0.0), 80.0);
+    }
+
+    #[test]
+    fn batch_progress_clamps_out_of_range_percent() {
+        let mut batch = BatchProgress::new(1);
+        assert_eq!(batch.update(0, 150.0), 100.0);
+    }
+
+    #[test]
+    fn batch_progress_complete_file_zeroes_its_eta() {
+        let mut batch = BatchProgress::new(2);
+        batch.update_eta(0, Some(30.0));
+        batch.update_eta(1, Some(20.0));
+        assert_eq!(batch.batch_eta_seconds(), Some(50.0));
+
+        batch.complete_file(0);
+        assert_eq!(batch.batch_eta_seconds(), Some(20.0));
+    }
+
+    #[test]
+    fn batch_progress_eta_is_none_until_a_file_reports_one() {
+        let mut batch = BatchProgress::new(2);
+        assert_eq!(batch.batch_eta_seconds(), None);
+        batch.update_eta(0, None);
+        assert_eq!(batch.batch_eta_seconds(), None);
+        batch.update_eta(0, Some(15.0));
+        assert_eq!(batch.batch_eta_seconds(), Some(15.0));
+    }
+}
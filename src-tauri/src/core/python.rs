@@ -1,12 +1,25 @@
 //! Python backend integration module.
 
+use crate::core::encode_settings::EncodeSettings;
 use crate::core::logging::log_message;
+use crate::core::probe::ProbeResult;
+use crate::core::progress::{BatchProgress, ProgressEvent, ThroughputEstimator, VersionedProgressEvent};
+use crate::core::sandbox_env;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
-use tauri::Manager;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::path::BaseDirectory;
+use tauri::{Emitter, Manager};
+
+/// Hard ceiling on concurrent backend processes regardless of what the caller
+/// requests or how many cores `available_parallelism` reports, so a batch of
+/// thousands of tiny files doesn't fork thousands of Python interpreters.
+const MAX_CONCURRENT_WORKERS: usize = 8;
 
 #[derive(Debug)]
 struct PythonResolution {
@@ -17,11 +30,33 @@ struct PythonResolution {
     uses_embedded: bool,
 }
 
+/// Bundled FFmpeg location resolved once per batch and shared by every worker.
+#[derive(Debug, Clone, Default)]
+struct FfmpegEnv {
+    binary: Option<PathBuf>,
+    bin_dir: Option<PathBuf>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ConvertPayload {
     pub files: Vec<String>,
     pub format: String,
     pub output: String,
+    /// Maximum number of backend processes to run at once. Omitted or `0`
+    /// falls back to `std::thread::available_parallelism()`, clamped to
+    /// [`MAX_CONCURRENT_WORKERS`].
+    #[serde(default)]
+    pub max_parallel_jobs: Option<usize>,
+    /// Structured codec/container/quality controls. When omitted, sane
+    /// defaults are derived from `format` (see [`EncodeSettings::resolve`]).
+    #[serde(default)]
+    pub encode_settings: Option<EncodeSettings>,
+    /// Probe results previously returned by `analyze_audio`, keyed by file
+    /// path. Used to pick sample-rate/channel defaults that don't upsample or
+    /// change channel layout unless explicitly requested, and to take a
+    /// passthrough fast path when the source already matches the target.
+    #[serde(default)]
+    pub probes: HashMap<String, ProbeResult>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -30,27 +65,340 @@ pub struct BackendResult {
     pub message: String,
     #[serde(default)]
     pub outputs: Vec<String>,
+    #[serde(default)]
+    pub failures: Vec<JobFailure>,
+}
+
+/// A single file's conversion failure, kept separate from a fatal batch-level
+/// `Err` so one bad file doesn't hide the results of the files that succeeded.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobFailure {
+    pub job_id: String,
+    pub file: String,
+    pub error: String,
+    pub kind: JobFailureKind,
+}
+
+/// Lets the frontend tell a user-initiated cancel apart from a real backend
+/// failure instead of pattern-matching an error string.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobFailureKind {
+    Cancelled,
+    Failed,
+}
+
+/// One file's worth of work handed to a worker thread.
+#[derive(Debug, Clone)]
+struct Job {
+    job_id: String,
+    file_index: usize,
+    file: String,
+}
+
+/// Internal per-job outcome distinguishing a user cancel from a real failure;
+/// collapsed into a [`JobFailure`] once the batch result is assembled.
+enum JobError {
+    Cancelled,
+    Failed(String),
+}
+
+impl From<String> for JobError {
+    fn from(error: String) -> Self {
+        JobError::Failed(error)
+    }
+}
+
+/// A conversion child process tracked so it can be cancelled mid-flight.
+struct ManagedJob {
+    child: Mutex<Child>,
+    cancelled: AtomicBool,
+}
+
+/// Tauri-managed registry of in-flight conversion children, keyed by `job_id`.
+#[derive(Default)]
+pub struct ChildRegistry(Mutex<HashMap<String, Arc<ManagedJob>>>);
+
+impl ChildRegistry {
+    fn register(&self, job_id: String, child: Child) -> Arc<ManagedJob> {
+        let managed = Arc::new(ManagedJob {
+            child: Mutex::new(child),
+            cancelled: AtomicBool::new(false),
+        });
+        self.0.lock().unwrap().insert(job_id, Arc::clone(&managed));
+        managed
+    }
+
+    fn unregister(&self, job_id: &str) {
+        self.0.lock().unwrap().remove(job_id);
+    }
+
+    fn get(&self, job_id: &str) -> Option<Arc<ManagedJob>> {
+        self.0.lock().unwrap().get(job_id).cloned()
+    }
+
+    fn job_ids(&self) -> Vec<String> {
+        self.0.lock().unwrap().keys().cloned().collect()
+    }
+}
+
+/// Unregisters a job from the [`ChildRegistry`] when dropped, so every early
+/// return from [`run_conversion_job`] (via `?`) still cleans up the entry.
+struct RegistryGuard<'a> {
+    registry: &'a ChildRegistry,
+    job_id: &'a str,
+}
+
+impl Drop for RegistryGuard<'_> {
+    fn drop(&mut self) {
+        self.registry.unregister(self.job_id);
+    }
+}
+
+/// Cancel a single in-flight conversion job. Kills the stored child (SIGTERM
+/// with a brief grace period, then SIGKILL) and emits `conversion-cancelled`.
+pub fn cancel_conversion(app: &tauri::AppHandle, job_id: &str) -> Result<(), String> {
+    let registry = app.state::<ChildRegistry>();
+    let managed = registry
+        .get(job_id)
+        .ok_or_else(|| format!("No running conversion with job id '{}'", job_id))?;
+
+    managed.cancelled.store(true, Ordering::SeqCst);
+    {
+        let mut child = managed.child.lock().unwrap();
+        terminate_child(&mut child);
+    }
+
+    let _ = app.emit("conversion-cancelled", serde_json::json!({ "job_id": job_id }));
+    Ok(())
+}
+
+/// Cancel every currently running conversion job. Returns how many were found.
+pub fn cancel_all_conversions(app: &tauri::AppHandle) -> usize {
+    let job_ids = app.state::<ChildRegistry>().job_ids();
+    for job_id in &job_ids {
+        let _ = cancel_conversion(app, job_id);
+    }
+    job_ids.len()
 }
 
-/// Execute Python backend with JSON input via stdin and stream progress events.
+#[cfg(unix)]
+fn terminate_child(child: &mut Child) {
+    // Signaling by PID is inherently racy if the child has already exited and
+    // the PID was reused, so recheck immediately before shelling out to
+    // `kill` rather than trusting an earlier observation of "still running".
+    if matches!(child.try_wait(), Ok(Some(_))) {
+        return;
+    }
+
+    let pid = child.id().to_string();
+    let _ = Command::new("kill").arg("-TERM").arg(&pid).status();
+
+    for _ in 0..20 {
+        if matches!(child.try_wait(), Ok(Some(_))) {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    let _ = child.kill();
+}
+
+#[cfg(not(unix))]
+fn terminate_child(child: &mut Child) {
+    let _ = child.kill();
+}
+
+fn resolve_worker_count(override_count: Option<usize>) -> usize {
+    let detected = std::thread::available_parallelism()
+        .map(std::num::NonZeroUsize::get)
+        .unwrap_or(1);
+
+    override_count
+        .filter(|n| *n > 0)
+        .unwrap_or(detected)
+        .clamp(1, MAX_CONCURRENT_WORKERS)
+}
+
+/// Split `payload.files` into per-file jobs and run up to N backend processes
+/// concurrently, aggregating each worker's `BackendResult.outputs` into one
+/// combined result. A queue shared behind a mutex (rather than a fixed
+/// per-thread split) keeps slower files from starving idle workers, while
+/// capping the thread count to N bounds how many children are alive at once.
 pub fn execute_python_conversion(
     app: tauri::AppHandle,
     payload: ConvertPayload,
 ) -> Result<BackendResult, String> {
-    let resolution = resolve_python(&app)?;
+    let resolution = Arc::new(resolve_python(&app)?);
+    let ffmpeg_env = Arc::new(resolve_ffmpeg_env(&app));
+    let explicit_encode_settings = Arc::new(payload.encode_settings.clone());
+    let probes = Arc::new(payload.probes.clone());
+
+    let jobs: VecDeque<Job> = payload
+        .files
+        .iter()
+        .enumerate()
+        .map(|(file_index, file)| Job {
+            job_id: format!("job-{}", file_index),
+            file_index,
+            file: file.clone(),
+        })
+        .collect();
+
+    let worker_count = resolve_worker_count(payload.max_parallel_jobs).min(jobs.len().max(1));
+    log_message(
+        "tauri",
+        &format!(
+            "Converting {} file(s) across {} worker(s)",
+            payload.files.len(),
+            worker_count
+        ),
+    );
+
+    let queue = Arc::new(Mutex::new(jobs));
+    let results: Arc<Mutex<Vec<(usize, Result<BackendResult, JobError>)>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    let batch_progress = Arc::new(Mutex::new(BatchProgress::new(payload.files.len())));
+
+    std::thread::scope(|scope| {
+        for _ in 0..worker_count {
+            let queue = Arc::clone(&queue);
+            let results = Arc::clone(&results);
+            let resolution = Arc::clone(&resolution);
+            let ffmpeg_env = Arc::clone(&ffmpeg_env);
+            let explicit_encode_settings = Arc::clone(&explicit_encode_settings);
+            let probes = Arc::clone(&probes);
+            let batch_progress = Arc::clone(&batch_progress);
+            let app = app.clone();
+            let format = payload.format.clone();
+            let output = payload.output.clone();
+
+            scope.spawn(move || loop {
+                let job = queue.lock().unwrap().pop_front();
+                let Some(job) = job else {
+                    break;
+                };
+
+                let encode_settings = EncodeSettings::resolve_for_file(
+                    (*explicit_encode_settings).clone(),
+                    &format,
+                    probes.get(&job.file),
+                );
+
+                let outcome = run_conversion_job(
+                    &app,
+                    &resolution,
+                    &ffmpeg_env,
+                    &encode_settings,
+                    probes.get(&job.file),
+                    &job,
+                    &format,
+                    &output,
+                    &batch_progress,
+                );
+                results.lock().unwrap().push((job.file_index, outcome));
+            });
+        }
+    });
+
+    let mut ordered = Arc::try_unwrap(results)
+        .map_err(|_| "Worker pool did not release its results".to_string())?
+        .into_inner()
+        .map_err(|_| "Worker results lock was poisoned".to_string())?;
+    ordered.sort_by_key(|(file_index, _)| *file_index);
+
+    let mut outputs = Vec::new();
+    let mut failures = Vec::new();
+    for (file_index, outcome) in ordered {
+        match outcome {
+            Ok(result) => outputs.extend(result.outputs),
+            Err(JobError::Cancelled) => failures.push(JobFailure {
+                job_id: format!("job-{}", file_index),
+                file: payload.files[file_index].clone(),
+                error: "Conversion cancelled by user".to_string(),
+                kind: JobFailureKind::Cancelled,
+            }),
+            Err(JobError::Failed(error)) => failures.push(JobFailure {
+                job_id: format!("job-{}", file_index),
+                file: payload.files[file_index].clone(),
+                error,
+                kind: JobFailureKind::Failed,
+            }),
+        }
+    }
+
+    let status = if failures.is_empty() {
+        "success"
+    } else if outputs.is_empty() {
+        if failures.iter().all(|f| f.kind == JobFailureKind::Cancelled) {
+            "cancelled"
+        } else {
+            "error"
+        }
+    } else {
+        "partial"
+    };
+
+    let batch_eta_seconds = batch_progress.lock().unwrap().batch_eta_seconds();
+    let _ = app.emit(
+        "conversion-progress",
+        VersionedProgressEvent::new(ProgressEvent::BatchComplete {
+            total_files: payload.files.len(),
+            completed_files: outputs.len(),
+            batch_eta_seconds,
+        }),
+    );
+
+    Ok(BackendResult {
+        status: status.to_string(),
+        message: format!(
+            "Converted {} of {} file(s)",
+            outputs.len(),
+            payload.files.len()
+        ),
+        outputs,
+        failures,
+    })
+}
+
+/// Run a single file through the Python backend and stream its progress,
+/// tagging every event with `job_id`/`file_index` so the frontend can tell
+/// workers apart even though they finish out of order.
+fn run_conversion_job(
+    app: &tauri::AppHandle,
+    resolution: &PythonResolution,
+    ffmpeg_env: &FfmpegEnv,
+    encode_settings: &EncodeSettings,
+    probe: Option<&ProbeResult>,
+    job: &Job,
+    format: &str,
+    output: &str,
+    batch_progress: &Mutex<BatchProgress>,
+) -> Result<BackendResult, JobError> {
+    let passthrough = probe.is_some_and(|probe| encode_settings.matches_source(probe));
 
     let json_input = serde_json::to_string(&serde_json::json!({
         "operation": "convert",
-        "files": payload.files,
-        "format": payload.format,
-        "output": payload.output,
+        "files": [job.file.clone()],
+        "format": format,
+        "output": output,
+        "codec": encode_settings.codec,
+        "container": encode_settings.container,
+        "bitrate_mode": encode_settings.bitrate_mode,
+        "bitrate_kbps": encode_settings.bitrate_kbps,
+        "quality": encode_settings.quality,
+        "sample_rate_hz": encode_settings.sample_rate_hz,
+        "channels": encode_settings.channels,
+        "loudness_target_lufs": encode_settings.loudness_target_lufs,
+        "passthrough": passthrough,
     }))
     .map_err(|e| format!("Failed to serialize request: {}", e))?;
 
     log_message(
         "tauri",
         &format!(
-            "Spawning python backend at {} (embedded={})",
+            "[{}] Spawning python backend at {} (embedded={})",
+            job.job_id,
             resolution.backend_path.display(),
             resolution.uses_embedded,
         ),
@@ -63,24 +411,257 @@ pub fn execute_python_conversion(
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
 
-    if let Some(bin_dir) = resolution.bin_dir.as_ref() {
-        let bin_dir_str = bin_dir.to_string_lossy().to_string();
-        command.env("SOUNDCONVERTER_BIN_DIR", &bin_dir_str);
+    sandbox_env::apply_sandbox_env_normalization(&mut command);
+    apply_bin_dir_env(&mut command, resolution.bin_dir.as_deref())?;
+    apply_ffmpeg_env(&mut command, ffmpeg_env)?;
 
-        if let Some(path) = std::env::var_os("PATH") {
-            let mut entries = std::env::split_paths(&path).collect::<Vec<_>>();
-            if !entries.contains(bin_dir) {
-                entries.insert(0, bin_dir.clone());
-                let merged = std::env::join_paths(entries)
-                    .map_err(|e| format!("Unable to join PATH entries: {}", e))?;
-                command.env("PATH", merged);
+    if let Some(python_home) = resolution.python_home.as_ref() {
+        command.env("PYTHONHOME", python_home);
+    }
+
+    command
+        .env("PYTHONUNBUFFERED", "1")
+        .env("PYTHONDONTWRITEBYTECODE", "1");
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to spawn Python process: {}", e))?;
+
+    let stdin_handle = child.stdin.take();
+    let stdout_handle = child.stdout.take();
+    let stderr_handle = child.stderr.take();
+
+    // Registered before stdin is written so the child is reachable (and
+    // killable) via `ChildRegistry` for its entire lifetime, including if the
+    // write below fails - otherwise a failed write would return early with an
+    // orphaned, unregistered process still running.
+    let registry = app.state::<ChildRegistry>();
+    let managed = registry.register(job.job_id.clone(), child);
+    let _guard = RegistryGuard {
+        registry: registry.inner(),
+        job_id: &job.job_id,
+    };
+
+    if let Some(mut stdin) = stdin_handle {
+        if let Err(e) = stdin.write_all(json_input.as_bytes()) {
+            terminate_child(&mut managed.child.lock().unwrap());
+            return Err(JobError::Failed(format!("Failed to write to stdin: {}", e)));
+        }
+    }
+
+    let stderr_handle = stderr_handle.map(|stderr| {
+        let job_id = job.job_id.clone();
+        std::thread::spawn(move || {
+            let reader = BufReader::new(stderr);
+            for line in reader.lines().map_while(Result::ok) {
+                log_message("python", &format!("[{}] {}", job_id, line));
             }
+        })
+    });
+
+    let mut final_result: Option<BackendResult> = None;
+    let mut last_stdout = String::new();
+    let mut throughput = ThroughputEstimator::new();
+
+    if let Some(stdout) = stdout_handle {
+        let reader = BufReader::new(stdout);
+        for line in reader.lines().map_while(Result::ok) {
+            let text = line.trim().to_string();
+            if text.is_empty() {
+                continue;
+            }
+            last_stdout = text.clone();
+
+            match serde_json::from_str::<Value>(&text) {
+                Ok(value) => {
+                    if let Some(event) =
+                        parse_progress_event(job, &value, &mut throughput, batch_progress)
+                    {
+                        if let Err(err) =
+                            app.emit("conversion-progress", VersionedProgressEvent::new(event))
+                        {
+                            log_message(
+                                "tauri",
+                                &format!("Failed to emit progress event: {}", err),
+                            );
+                        }
+                    }
+
+                    if let Some(status) = value
+                        .get("event")
+                        .and_then(|event| event.as_str())
+                        .filter(|event| *event == "complete")
+                    {
+                        let outputs = value
+                            .get("outputs")
+                            .and_then(|raw| serde_json::from_value(raw.clone()).ok())
+                            .unwrap_or_default();
+                        let message = value
+                            .get("message")
+                            .and_then(|raw| raw.as_str())
+                            .unwrap_or_default()
+                            .to_string();
+
+                        final_result = Some(BackendResult {
+                            status: value
+                                .get("status")
+                                .and_then(|s| s.as_str())
+                                .unwrap_or(status)
+                                .to_string(),
+                            message,
+                            outputs,
+                            failures: Vec::new(),
+                        });
+                    }
+                }
+                Err(err) => {
+                    log_message(
+                        "tauri",
+                        &format!(
+                            "[{}] Failed to parse python output '{}': {}",
+                            job.job_id, text, err
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    if let Some(handle) = stderr_handle {
+        let _ = handle.join();
+    }
+
+    let status = managed
+        .child
+        .lock()
+        .unwrap()
+        .wait()
+        .map_err(|e| format!("Failed to wait for Python process: {}", e))?;
+
+    if !status.success() {
+        if managed.cancelled.load(Ordering::SeqCst) {
+            return Err(JobError::Cancelled);
+        }
+
+        let code = status.code().unwrap_or(-1);
+        let message = if last_stdout.is_empty() {
+            format!("Python process failed with exit code {}", code)
         } else {
-            command.env("PATH", &bin_dir_str);
+            format!(
+                "Python process failed with exit code {}: {}",
+                code, last_stdout
+            )
+        };
+        return Err(JobError::Failed(message));
+    }
+
+    final_result
+        .ok_or_else(|| JobError::Failed("Python backend did not return a final status".to_string()))
+}
+
+/// Tolerantly turn one line of raw backend JSON into a [`ProgressEvent`],
+/// folding in the per-file ETA and the shared batch percentage/ETA. Returns
+/// `None` for lines that don't carry recognizable progress (e.g. the
+/// backend's own diagnostic chatter), so unknown keys never break the
+/// stream.
+fn parse_progress_event(
+    job: &Job,
+    value: &Value,
+    throughput: &mut ThroughputEstimator,
+    batch_progress: &Mutex<BatchProgress>,
+) -> Option<ProgressEvent> {
+    let event_name = value.get("event").and_then(Value::as_str).unwrap_or("progress");
+
+    match event_name {
+        "start" | "started" => Some(ProgressEvent::Started {
+            job_id: job.job_id.clone(),
+            file_index: job.file_index,
+            file: job.file.clone(),
+        }),
+        "error" => Some(ProgressEvent::Error {
+            job_id: job.job_id.clone(),
+            file_index: job.file_index,
+            file: job.file.clone(),
+            message: value
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("Unknown backend error")
+                .to_string(),
+        }),
+        "complete" => {
+            let batch_percent = batch_progress.lock().unwrap().complete_file(job.file_index);
+            Some(ProgressEvent::FileComplete {
+                job_id: job.job_id.clone(),
+                file_index: job.file_index,
+                file: job.file.clone(),
+                batch_percent,
+            })
+        }
+        _ => {
+            let percent = value
+                .get("percent")
+                .or_else(|| value.get("progress"))
+                .and_then(Value::as_f64)
+                .map(|percent| percent as f32)?
+                .clamp(0.0, 100.0);
+            let processed_seconds = value
+                .get("processed_seconds")
+                .and_then(Value::as_f64)
+                .map(|seconds| seconds as f32);
+            let total_seconds = value
+                .get("total_seconds")
+                .and_then(Value::as_f64)
+                .map(|seconds| seconds as f32);
+
+            let eta_seconds =
+                processed_seconds.and_then(|processed| throughput.observe(processed, total_seconds));
+
+            let mut batch_progress = batch_progress.lock().unwrap();
+            let batch_percent = batch_progress.update(job.file_index, percent);
+            let batch_eta_seconds = batch_progress.update_eta(job.file_index, eta_seconds);
+            drop(batch_progress);
+
+            Some(ProgressEvent::Tick {
+                job_id: job.job_id.clone(),
+                file_index: job.file_index,
+                file: job.file.clone(),
+                percent,
+                processed_seconds,
+                total_seconds,
+                eta_seconds,
+                batch_percent,
+                batch_eta_seconds,
+            })
+        }
+    }
+}
+
+/// Prepend `bin_dir` to `PATH` and advertise it via `SOUNDCONVERTER_BIN_DIR`.
+fn apply_bin_dir_env(command: &mut Command, bin_dir: Option<&Path>) -> Result<(), String> {
+    let Some(bin_dir) = bin_dir else {
+        return Ok(());
+    };
+
+    let bin_dir_str = bin_dir.to_string_lossy().to_string();
+    command.env("SOUNDCONVERTER_BIN_DIR", &bin_dir_str);
+
+    if let Some(path) = std::env::var_os("PATH") {
+        let mut entries = std::env::split_paths(&path).collect::<Vec<_>>();
+        if !entries.iter().any(|entry| entry == bin_dir) {
+            entries.insert(0, bin_dir.to_path_buf());
+            let merged = std::env::join_paths(entries)
+                .map_err(|e| format!("Unable to join PATH entries: {}", e))?;
+            command.env("PATH", merged);
         }
+    } else {
+        command.env("PATH", &bin_dir_str);
     }
 
-    // Resolve bundled FFmpeg sidecar based on target platform
+    Ok(())
+}
+
+/// Resolve the bundled FFmpeg sidecar for the current platform once per batch.
+fn resolve_ffmpeg_env(app: &tauri::AppHandle) -> FfmpegEnv {
     let ffmpeg_binary_name = if cfg!(target_os = "windows") {
         "ffmpeg-x86_64-pc-windows-msvc.exe"
     } else if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
@@ -96,8 +677,10 @@ pub fn execute_python_conversion(
     };
 
     let ffmpeg_resource_path = format!("binaries/{}", ffmpeg_binary_name);
-
-    let mut ffmpeg_path_opt = app.path_resolver().resolve_resource(&ffmpeg_resource_path);
+    let mut ffmpeg_path_opt = app
+        .path()
+        .resolve(&ffmpeg_resource_path, BaseDirectory::Resource)
+        .ok();
 
     // In dev mode, if resource resolution fails, try direct filesystem path
     if ffmpeg_path_opt.is_none()
@@ -107,7 +690,6 @@ pub fn execute_python_conversion(
             .unwrap_or(false)
     {
         if cfg!(debug_assertions) {
-            // Try relative to current working directory (dev mode)
             let dev_path = std::env::current_dir().ok().map(|cwd| {
                 cwd.join("src-tauri")
                     .join("binaries")
@@ -126,8 +708,8 @@ pub fn execute_python_conversion(
         }
     }
 
-    if let Some(ffmpeg_path) = ffmpeg_path_opt {
-        if ffmpeg_path.exists() {
+    match ffmpeg_path_opt {
+        Some(ffmpeg_path) if ffmpeg_path.exists() => {
             log_message(
                 "tauri",
                 &format!(
@@ -136,45 +718,88 @@ pub fn execute_python_conversion(
                     ffmpeg_path.display()
                 ),
             );
+            let bin_dir = ffmpeg_path.parent().map(|p| p.to_path_buf());
+            FfmpegEnv {
+                binary: Some(ffmpeg_path),
+                bin_dir,
+            }
+        }
+        Some(ffmpeg_path) => {
+            log_message(
+                "tauri",
+                &format!("FFmpeg binary not found at: {}", ffmpeg_path.display()),
+            );
+            FfmpegEnv::default()
+        }
+        None => {
+            log_message(
+                "tauri",
+                &format!(
+                    "FFmpeg binary not found for resource path: {}",
+                    ffmpeg_resource_path
+                ),
+            );
+            FfmpegEnv::default()
+        }
+    }
+}
 
-            // Set FFMPEG_BINARY to the exact binary path (highest priority)
-            command.env("FFMPEG_BINARY", &ffmpeg_path);
+/// Set `FFMPEG_BINARY` to the exact bundled binary and also add its directory
+/// to `PATH` as a fallback for anything that shells out to `ffmpeg` by name.
+fn apply_ffmpeg_env(command: &mut Command, ffmpeg: &FfmpegEnv) -> Result<(), String> {
+    let Some(binary) = ffmpeg.binary.as_ref() else {
+        return Ok(());
+    };
 
-            // Also add to PATH for fallback
-            if let Some(ffmpeg_bin_dir) = ffmpeg_path.parent() {
-                let ffmpeg_bin_str = ffmpeg_bin_dir.to_string_lossy().to_string();
+    command.env("FFMPEG_BINARY", binary);
 
-                if let Some(current_path) = std::env::var_os("PATH") {
-                    let mut entries = std::env::split_paths(&current_path).collect::<Vec<_>>();
-                    let ffmpeg_pathbuf = ffmpeg_bin_dir.to_path_buf();
-                    if !entries.contains(&ffmpeg_pathbuf) {
-                        entries.insert(0, ffmpeg_pathbuf);
-                        if let Ok(merged) = std::env::join_paths(entries) {
-                            command.env("PATH", merged);
-                        }
-                    }
-                } else {
-                    command.env("PATH", &ffmpeg_bin_str);
-                }
+    if let Some(bin_dir) = ffmpeg.bin_dir.as_ref() {
+        let bin_dir_str = bin_dir.to_string_lossy().to_string();
 
-                command.env("SOUNDCONVERTER_BIN_DIR", &ffmpeg_bin_str);
+        if let Some(current_path) = std::env::var_os("PATH") {
+            let mut entries = std::env::split_paths(&current_path).collect::<Vec<_>>();
+            if !entries.iter().any(|entry| entry == bin_dir) {
+                entries.insert(0, bin_dir.clone());
+                let merged = std::env::join_paths(entries)
+                    .map_err(|e| format!("Unable to join PATH entries: {}", e))?;
+                command.env("PATH", merged);
             }
         } else {
-            log_message(
-                "tauri",
-                &format!("FFmpeg binary not found at: {}", ffmpeg_path.display()),
-            );
+            command.env("PATH", &bin_dir_str);
         }
-    } else {
-        log_message(
-            "tauri",
-            &format!(
-                "FFmpeg binary not found for resource path: {}",
-                ffmpeg_resource_path
-            ),
-        );
+
+        command.env("SOUNDCONVERTER_BIN_DIR", &bin_dir_str);
     }
 
+    Ok(())
+}
+
+/// Run a single `"probe"` round-trip through the Python backend, parsing its
+/// `results` array into [`ProbeResult`]s so the frontend can pick smart
+/// defaults (and warn on lossy-to-lossless "fake upgrades") before converting.
+/// Used by `commands::analyze_audio`.
+pub fn execute_python_probe(
+    app: tauri::AppHandle,
+    files: Vec<String>,
+) -> Result<Vec<ProbeResult>, String> {
+    let resolution = resolve_python(&app)?;
+
+    let json_input = serde_json::to_string(&serde_json::json!({
+        "operation": "probe",
+        "files": files,
+    }))
+    .map_err(|e| format!("Failed to serialize request: {}", e))?;
+
+    let mut command = Command::new(&resolution.command);
+    command
+        .arg(&resolution.backend_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    sandbox_env::apply_sandbox_env_normalization(&mut command);
+    apply_bin_dir_env(&mut command, resolution.bin_dir.as_deref())?;
+
     if let Some(python_home) = resolution.python_home.as_ref() {
         command.env("PYTHONHOME", python_home);
     }
@@ -196,70 +821,26 @@ pub fn execute_python_conversion(
     let stderr_handle = child.stderr.take().map(|stderr| {
         std::thread::spawn(move || {
             let reader = BufReader::new(stderr);
-            for line in reader.lines() {
-                if let Ok(text) = line {
-                    log_message("python", &text);
-                }
+            for line in reader.lines().map_while(Result::ok) {
+                log_message("python", &line);
             }
         })
     });
 
-    let mut final_result: Option<BackendResult> = None;
-    let mut last_stdout = String::new();
-
+    let mut last_line: Option<Value> = None;
     if let Some(stdout) = child.stdout.take() {
         let reader = BufReader::new(stdout);
-        for line in reader.lines() {
-            if let Ok(mut text) = line {
-                if text.trim().is_empty() {
-                    continue;
-                }
-
-                text = text.trim().to_string();
-                last_stdout = text.clone();
-
-                match serde_json::from_str::<Value>(&text) {
-                    Ok(value) => {
-                        if let Err(err) = app.emit_all("conversion-progress", value.clone()) {
-                            log_message(
-                                "tauri",
-                                &format!("Failed to emit progress event: {}", err),
-                            );
-                        }
-
-                        if let Some(status) = value
-                            .get("event")
-                            .and_then(|event| event.as_str())
-                            .filter(|event| *event == "complete")
-                        {
-                            let outputs = value
-                                .get("outputs")
-                                .and_then(|raw| serde_json::from_value(raw.clone()).ok())
-                                .unwrap_or_default();
-                            let message = value
-                                .get("message")
-                                .and_then(|raw| raw.as_str())
-                                .unwrap_or_default()
-                                .to_string();
-
-                            final_result = Some(BackendResult {
-                                status: value
-                                    .get("status")
-                                    .and_then(|s| s.as_str())
-                                    .unwrap_or(status)
-                                    .to_string(),
-                                message,
-                                outputs,
-                            });
-                        }
-                    }
-                    Err(err) => {
-                        log_message(
-                            "tauri",
-                            &format!("Failed to parse python output '{}': {}", text, err),
-                        );
-                    }
-                }
+        for line in reader.lines().map_while(Result::ok) {
+            let text = line.trim().to_string();
+            if text.is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Value>(&text) {
+                Ok(value) => last_line = Some(value),
+                Err(err) => log_message(
+                    "tauri",
+                    &format!("Failed to parse python output '{}': {}", text, err),
+                ),
             }
         }
     }
@@ -274,18 +855,18 @@ pub fn execute_python_conversion(
 
     if !status.success() {
         let code = status.code().unwrap_or(-1);
-        let message = if last_stdout.is_empty() {
-            format!("Python process failed with exit code {}", code)
-        } else {
-            format!(
-                "Python process failed with exit code {}: {}",
-                code, last_stdout
-            )
-        };
-        return Err(message);
+        return Err(format!("Python process failed with exit code {}", code));
     }
 
-    final_result.ok_or_else(|| "Python backend did not return a final status".to_string())
+    let response =
+        last_line.ok_or_else(|| "Python backend did not return a probe result".to_string())?;
+
+    let results = response
+        .get("results")
+        .cloned()
+        .unwrap_or(response);
+
+    serde_json::from_value(results).map_err(|e| format!("Failed to parse probe results: {}", e))
 }
 
 fn resolve_python(app: &tauri::AppHandle) -> Result<PythonResolution, String> {
@@ -296,7 +877,7 @@ fn resolve_python(app: &tauri::AppHandle) -> Result<PythonResolution, String> {
 
     let backend_candidates = vec![
         // Production: bundled resource
-        app.path_resolver().resolve_resource("backend/main.py"),
+        app.path().resolve("backend/main.py", BaseDirectory::Resource).ok(),
         // Dev mode: relative to project root
         std::env::current_dir()
             .ok()
@@ -329,12 +910,14 @@ fn resolve_python(app: &tauri::AppHandle) -> Result<PythonResolution, String> {
     );
 
     // Try new binaries/ location first (for Phase 4 bundled Python)
-    let binaries_root = app.path_resolver().resolve_resource("binaries");
+    let binaries_root = app.path().resolve("binaries", BaseDirectory::Resource).ok();
 
     // Then try old bin/ locations (for backward compatibility)
     let bin_root_candidates = [
-        app.path_resolver().resolve_resource("bin"),
-        app.path_resolver().resolve_resource("src-tauri/bin"),
+        app.path().resolve("bin", BaseDirectory::Resource).ok(),
+        app.path()
+            .resolve("src-tauri/bin", BaseDirectory::Resource)
+            .ok(),
     ];
 
     let bin_root = bin_root_candidates
@@ -437,3 +1020,29 @@ fn derive_python_home(python_bin: &Path) -> Option<PathBuf> {
         Some(parent.to_path_buf())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_worker_count_clamps_an_override_above_the_max() {
+        assert_eq!(resolve_worker_count(Some(1_000)), MAX_CONCURRENT_WORKERS);
+    }
+
+    #[test]
+    fn resolve_worker_count_keeps_a_small_explicit_override() {
+        assert_eq!(resolve_worker_count(Some(2)), 2);
+    }
+
+    #[test]
+    fn resolve_worker_count_treats_zero_override_as_unset() {
+        assert_eq!(resolve_worker_count(Some(0)), resolve_worker_count(None));
+    }
+
+    #[test]
+    fn resolve_worker_count_without_an_override_stays_within_bounds() {
+        let detected = resolve_worker_count(None);
+        assert!((1..=MAX_CONCURRENT_WORKERS).contains(&detected));
+    }
+}
@@ -0,0 +1,143 @@
+//! Strips sandbox-injected library/module paths before spawning the bundled
+//! FFmpeg/Python backend on Linux.
+//!
+//! Packaging formats like AppImage, Flatpak, and Snap inject `LD_LIBRARY_PATH`,
+//! `GST_PLUGIN_PATH`, `PYTHONPATH`, and GTK/GIO search paths that point inside
+//! the sandbox mount so the *wrapper* resolves its own shared libraries. If we
+//! hand those down to a spawned child unchanged, the child picks up the
+//! sandbox's libraries instead of the host's (or its own bundled ones) and
+//! fails to start.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Environment variables known to carry OS-style path lists that can leak
+/// sandbox-internal entries into a spawned child.
+const PATH_LIKE_VARS: &[&str] = &[
+    "LD_LIBRARY_PATH",
+    "GST_PLUGIN_PATH",
+    "PYTHONPATH",
+    "GIO_MODULE_DIR",
+    "GTK_PATH",
+    "GSETTINGS_SCHEMA_DIR",
+];
+
+pub fn is_appimage() -> bool {
+    std::env::var_os("APPIMAGE").is_some() || std::env::var_os("APPDIR").is_some()
+}
+
+pub fn is_flatpak() -> bool {
+    Path::new("/.flatpak-info").exists()
+}
+
+pub fn is_snap() -> bool {
+    std::env::var_os("SNAP").is_some()
+}
+
+/// The sandbox mount root to strip path-list entries under, if we're running
+/// inside one of the packaging formats this module knows about.
+fn sandbox_root() -> Option<PathBuf> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    if let Some(appdir) = std::env::var_os("APPDIR") {
+        return Some(PathBuf::from(appdir));
+    }
+    if is_flatpak() {
+        return Some(PathBuf::from("/app"));
+    }
+    if let Some(snap) = std::env::var_os("SNAP") {
+        return Some(PathBuf::from(snap));
+    }
+
+    None
+}
+
+/// Split `value` on the platform path separator, drop empty entries and every
+/// entry that lives under `sandbox_root`, and de-duplicate while keeping the *last* occurrence
+/// of each remaining entry (so a later, more specific path wins).
+pub fn normalize_pathlist(value: &str, sandbox_root: &Path) -> Vec<PathBuf> {
+    let mut kept: Vec<PathBuf> = Vec::new();
+
+    for entry in std::env::split_paths(value) {
+        if entry.as_os_str().is_empty() || entry.starts_with(sandbox_root) {
+            continue;
+        }
+        if let Some(pos) = kept.iter().position(|existing| existing == &entry) {
+            kept.remove(pos);
+        }
+        kept.push(entry);
+    }
+
+    kept
+}
+
+/// Strip sandbox-internal entries from every [`PATH_LIKE_VARS`] variable
+/// before `command` spawns, normalizing a `*_ORIG`/`*_VFS` backup the launcher
+/// saved (the pre-sandbox value) in preference to the current one. A variable
+/// that normalizes down to nothing is unset entirely rather than left as `""`,
+/// since an empty `LD_LIBRARY_PATH` is not the same as an absent one to the
+/// dynamic linker.
+pub fn apply_sandbox_env_normalization(command: &mut Command) {
+    let Some(root) = sandbox_root() else {
+        return;
+    };
+
+    for var in PATH_LIKE_VARS {
+        let backup = std::env::var(format!("{}_ORIG", var))
+            .or_else(|_| std::env::var(format!("{}_VFS", var)))
+            .ok();
+        let Some(raw) = backup.or_else(|| std::env::var(var).ok()) else {
+            continue;
+        };
+
+        let normalized = normalize_pathlist(&raw, &root);
+        if normalized.is_empty() {
+            command.env_remove(var);
+        } else if let Ok(joined) = std::env::join_paths(&normalized) {
+            command.env(var, joined);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_pathlist_empty_input_is_empty_output() {
+        let root = Path::new("/app");
+        assert_eq!(normalize_pathlist("", root), Vec::<PathBuf>::new());
+    }
+
+    #[test]
+    fn normalize_pathlist_strips_entries_under_sandbox_root() {
+        let root = Path::new("/app");
+        let value = "/app/lib:/usr/lib:/app/lib64";
+        assert_eq!(
+            normalize_pathlist(value, root),
+            vec![PathBuf::from("/usr/lib")]
+        );
+    }
+
+    #[test]
+    fn normalize_pathlist_dedups_keeping_the_last_occurrence() {
+        let root = Path::new("/app");
+        let value = "/usr/lib:/opt/lib:/usr/lib";
+        assert_eq!(
+            normalize_pathlist(value, root),
+            vec![PathBuf::from("/opt/lib"), PathBuf::from("/usr/lib")]
+        );
+    }
+
+    #[test]
+    fn normalize_pathlist_keeps_non_sandbox_entries_in_order() {
+        let root = Path::new("/snap/soundconverter/current");
+        let value = "/usr/lib:/usr/local/lib";
+        assert_eq!(
+            normalize_pathlist(value, root),
+            vec![PathBuf::from("/usr/lib"), PathBuf::from("/usr/local/lib")]
+        );
+    }
+}
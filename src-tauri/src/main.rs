@@ -1,12 +1,25 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 
 mod commands;
 mod core;
 
 fn main() {
     tauri::Builder::default()
+        // Must be registered before any other plugin so a second launch (e.g.
+        // double-clicking a file while the app is already open) is caught and
+        // forwarded here instead of spawning a second window.
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            let paths = core::file_association::extract_audio_paths(&args);
+            if !paths.is_empty() {
+                let _ = app.emit("request-open-file", paths);
+            }
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.set_focus();
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
@@ -15,7 +28,26 @@ fn main() {
         .plugin(tauri_plugin_log::Builder::default().build())
         .plugin(tauri_plugin_updater::Builder::default().build())
         .plugin(tauri_plugin_process::init())
+        .manage(core::python::ChildRegistry::default())
         .setup(|app| {
+            let startup_paths =
+                core::file_association::extract_audio_paths(std::env::args());
+            if !startup_paths.is_empty() {
+                let _ = app.emit("request-open-file", startup_paths);
+            }
+
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+                let handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    let urls = event.urls().iter().map(|url| url.to_string());
+                    let paths = core::file_association::extract_audio_paths_from_urls(urls);
+                    if !paths.is_empty() {
+                        let _ = handle.emit("request-open-file", paths);
+                    }
+                });
+            }
+
             #[cfg(desktop)]
             {
                 use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
@@ -85,7 +117,9 @@ fn main() {
 
                 app.on_menu_event(|app, event| {
                     if event.id() == "open_file" {
-                        let _ = app.emit("request-open-file", ());
+                        // No path yet: the frontend responds to an empty list
+                        // by showing its own file picker.
+                        let _ = app.emit("request-open-file", Vec::<std::path::PathBuf>::new());
                     }
                 });
             }
@@ -94,13 +128,16 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             commands::ping,
             commands::convert_audio,
-            commands::analyze_audio
+            commands::analyze_audio,
+            commands::resolve_encode_preset,
+            commands::cancel_conversion,
+            commands::cancel_all_conversions
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
         .run(|_app, _event| {
-            // RunEvent handling removed as RunEvent::Opened doesn't exist in Tauri v2
-            // File opening/deep linking should be implemented using tauri-plugin-deep-link
-            // or command-line argument handling if needed
+            // File-association handling lives in `setup` (startup args) and the
+            // `tauri-plugin-single-instance` callback (a second launch); there's
+            // no RunEvent::Opened in Tauri v2 to hook here.
         });
 }